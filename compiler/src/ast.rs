@@ -0,0 +1,28 @@
+/// The parsed representation of a whole source file: the subset of C this
+/// compiler understands is just a list of function definitions.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Program {
+    pub functions: Vec<Function>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Function {
+    pub return_type: Type,
+    pub name: String,
+    pub body: Vec<Statement>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Type {
+    Int,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Statement {
+    Return(Expression),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Expression {
+    Constant(u64),
+}
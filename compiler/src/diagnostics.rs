@@ -0,0 +1,113 @@
+use std::error::Error;
+use std::fmt;
+
+use crate::span::Span;
+
+/// Anything that can be rendered as a codespan-style report: a message
+/// plus the span of source it concerns.
+pub trait Diagnostic {
+    fn message(&self) -> &str;
+    fn span(&self) -> Span;
+}
+
+/// A single lexing failure, tied to the span of the offending input.
+#[derive(Debug)]
+pub struct LexError {
+    pub message: String,
+    pub span: Span,
+}
+
+impl LexError {
+    pub fn new(message: impl Into<String>, span: Span) -> Self {
+        LexError {
+            message: message.into(),
+            span,
+        }
+    }
+}
+
+impl fmt::Display for LexError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.message)
+    }
+}
+
+impl Error for LexError {}
+
+impl Diagnostic for LexError {
+    fn message(&self) -> &str {
+        &self.message
+    }
+
+    fn span(&self) -> Span {
+        self.span
+    }
+}
+
+/// A single parsing failure, tied to the span of the offending token (or
+/// of the last token lexed, if the parser ran out of input).
+#[derive(Debug)]
+pub struct ParseError {
+    pub message: String,
+    pub span: Span,
+}
+
+impl ParseError {
+    pub fn new(message: impl Into<String>, span: Span) -> Self {
+        ParseError {
+            message: message.into(),
+            span,
+        }
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.message)
+    }
+}
+
+impl Error for ParseError {}
+
+impl Diagnostic for ParseError {
+    fn message(&self) -> &str {
+        &self.message
+    }
+
+    fn span(&self) -> Span {
+        self.span
+    }
+}
+
+/// Render a diagnostic as a codespan-style report: the file name, the
+/// offending line, and a caret underline beneath the faulty span.
+pub fn report(file_name: &str, source: &str, error: &dyn Diagnostic) -> String {
+    let span = error.span();
+    let line_no = span.line;
+    let line_text = source.lines().nth(line_no - 1).unwrap_or("");
+
+    // A span that runs past the end of `line_text` (e.g. an unterminated
+    // comment or string literal, whose span reaches all the way to EOF)
+    // can't be underlined past the visible line, so clamp to what's left
+    // of it rather than printing carets for bytes on later lines.
+    let visible_width = line_text
+        .len()
+        .saturating_sub(span.column.saturating_sub(1));
+    let underline_width = (span.end - span.start).max(1).min(visible_width.max(1));
+
+    let gutter = line_no.to_string();
+    let gutter_pad = " ".repeat(gutter.len());
+    let caret_pad = " ".repeat(span.column.saturating_sub(1));
+    let underline = "^".repeat(underline_width);
+
+    let mut out = String::new();
+    out.push_str(&format!("error: {}\n", error.message()));
+    out.push_str(&format!(
+        "{} --> {}:{}:{}\n",
+        gutter_pad, file_name, line_no, span.column
+    ));
+    out.push_str(&format!("{} |\n", gutter_pad));
+    out.push_str(&format!("{} | {}\n", gutter, line_text));
+    out.push_str(&format!("{} | {}{}\n", gutter_pad, caret_pad, underline));
+    out
+}
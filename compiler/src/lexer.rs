@@ -0,0 +1,486 @@
+use crate::span::Span;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum TokenKind {
+    OpenBrace,
+    CloseBrace,
+    OpenParenthesis,
+    CloseParenthesis,
+    Semicolon,
+    IntKeyword,
+    ReturnKeyword,
+    Identifier(String),
+    Integer(u64),
+    StringLiteral(String),
+    CharLiteral(char),
+    /// Input that doesn't form a valid token, including a string or
+    /// character literal that ran past end of input without a closing
+    /// delimiter. Carries a human-readable description of what went
+    /// wrong so lexing can keep going past it instead of aborting,
+    /// letting callers collect every lexical error in a file in a
+    /// single pass.
+    Unknown(String),
+    /// A `//` or `/* */` comment, raw delimiters included. Only produced
+    /// when the tokenizer is built with `keep_comments`; otherwise
+    /// comments are skipped like whitespace.
+    Comment(String),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Token {
+    pub kind: TokenKind,
+    pub span: Span,
+}
+
+fn string_to_token(s: &str) -> TokenKind {
+    use TokenKind::*;
+    match s {
+        "Int" => IntKeyword,
+        "Return" => ReturnKeyword,
+        s => Identifier(s.to_string()),
+    }
+}
+
+/// Lazily tokenizes a `&str`, producing one `Token` per `next()` call.
+/// Unlike the previous `io::BufRead`-consuming tokenizer, this borrows the
+/// source directly (so lexing an in-memory string needs no reader), and
+/// invalid input is folded into `TokenKind::Unknown` rather than aborting
+/// the whole stream.
+pub struct Tokenizer<'a> {
+    chars: std::str::Chars<'a>,
+    offset: usize,
+    line: usize,
+    column: usize,
+    emit_comments: bool,
+}
+
+impl<'a> Tokenizer<'a> {
+    pub fn new(source: &'a str) -> Self {
+        Tokenizer {
+            chars: source.chars(),
+            offset: 0,
+            line: 1,
+            column: 1,
+            emit_comments: false,
+        }
+    }
+
+    /// Yields comments as `TokenKind::Comment` tokens instead of skipping
+    /// them, for doc-extraction tooling that wants to keep them.
+    pub fn keep_comments(mut self) -> Self {
+        self.emit_comments = true;
+        self
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.peek_at(0)
+    }
+
+    fn peek_at(&self, n: usize) -> Option<char> {
+        self.chars.clone().nth(n)
+    }
+
+    fn advance(&mut self) -> Option<char> {
+        let ch = self.chars.next()?;
+        self.offset += ch.len_utf8();
+        if ch == '\n' {
+            self.line += 1;
+            self.column = 1;
+        } else {
+            self.column += 1;
+        }
+        Some(ch)
+    }
+
+    fn is_comment_start(&self) -> bool {
+        self.peek() == Some('/') && matches!(self.peek_at(1), Some('/') | Some('*'))
+    }
+
+    /// Consumes a `//` or `/* */` comment without keeping its text,
+    /// called when `emit_comments` is off. Assumes `is_comment_start()`.
+    /// Returns `false` if a block comment ran past end of input without
+    /// a closing `*/`.
+    fn skip_comment(&mut self) -> bool {
+        self.advance(); // leading '/'
+        match self.advance() {
+            Some('/') => {
+                while !matches!(self.peek(), None | Some('\n')) {
+                    self.advance();
+                }
+                true
+            }
+            Some('*') => loop {
+                match self.advance() {
+                    None => break false,
+                    Some('*') if self.peek() == Some('/') => {
+                        self.advance();
+                        break true;
+                    }
+                    _ => {}
+                }
+            },
+            _ => unreachable!("skip_comment called without a comment opener"),
+        }
+    }
+
+    /// Consumes a `//` or `/* */` comment, keeping its raw text including
+    /// delimiters. The leading `/` has already been consumed as `first`.
+    /// An unterminated block comment is reported as `TokenKind::Unknown`
+    /// rather than silently swallowed to end of input.
+    fn read_comment(&mut self) -> TokenKind {
+        let mut text = String::from('/');
+        match self.advance() {
+            Some('/') => {
+                text.push('/');
+                while !matches!(self.peek(), None | Some('\n')) {
+                    text.push(self.advance().unwrap());
+                }
+                TokenKind::Comment(text)
+            }
+            Some('*') => {
+                text.push('*');
+                loop {
+                    match self.advance() {
+                        None => return TokenKind::Unknown("unterminated block comment".to_string()),
+                        Some(c) => {
+                            text.push(c);
+                            if c == '*' && self.peek() == Some('/') {
+                                text.push(self.advance().unwrap());
+                                return TokenKind::Comment(text);
+                            }
+                        }
+                    }
+                }
+            }
+            _ => unreachable!("read_comment called without a comment opener"),
+        }
+    }
+
+    /// Decodes the escape sequence introduced by a backslash, consuming
+    /// any extra characters it needs (`\xNN` reads two more). An
+    /// unrecognized escape (anything but the cases below) is not an
+    /// error: `c` itself is returned verbatim. A malformed `\xNN` --
+    /// fewer than two hex digits following, or digits that don't decode
+    /// to a valid char -- is an error, since silently substituting some
+    /// other character would corrupt the literal without saying so.
+    fn decode_escape(&mut self, c: char) -> Result<char, String> {
+        match c {
+            'n' => Ok('\n'),
+            't' => Ok('\t'),
+            '\\' => Ok('\\'),
+            '"' => Ok('"'),
+            '\'' => Ok('\''),
+            '0' => Ok('\0'),
+            'x' => match (self.advance(), self.advance()) {
+                (Some(hi), Some(lo)) => u32::from_str_radix(&format!("{}{}", hi, lo), 16)
+                    .ok()
+                    .filter(|_| hi.is_ascii_hexdigit() && lo.is_ascii_hexdigit())
+                    .and_then(char::from_u32)
+                    .ok_or_else(|| format!("invalid hex escape: \\x{}{}", hi, lo)),
+                _ => Err("invalid hex escape: unexpected end of input".to_string()),
+            },
+            other => Ok(other),
+        }
+    }
+
+    /// Reads a `"..."` string literal, decoding escapes. The opening `"`
+    /// has already been consumed as `first`.
+    fn read_string_literal(&mut self) -> TokenKind {
+        let mut value = String::new();
+        loop {
+            match self.advance() {
+                None => return TokenKind::Unknown("unterminated string literal".to_string()),
+                Some('"') => return TokenKind::StringLiteral(value),
+                Some('\\') => match self.advance() {
+                    Some(escaped) => match self.decode_escape(escaped) {
+                        Ok(decoded) => value.push(decoded),
+                        Err(message) => return TokenKind::Unknown(message),
+                    },
+                    None => return TokenKind::Unknown("unterminated string literal".to_string()),
+                },
+                Some(c) => value.push(c),
+            }
+        }
+    }
+
+    /// Reads a `'c'` character literal, decoding a leading escape if
+    /// present. The opening `'` has already been consumed as `first`.
+    fn read_char_literal(&mut self) -> TokenKind {
+        let value = match self.advance() {
+            None => return TokenKind::Unknown("unterminated character literal".to_string()),
+            Some('\\') => match self.advance() {
+                Some(escaped) => match self.decode_escape(escaped) {
+                    Ok(decoded) => decoded,
+                    Err(message) => return TokenKind::Unknown(message),
+                },
+                None => return TokenKind::Unknown("unterminated character literal".to_string()),
+            },
+            Some(c) => c,
+        };
+
+        match self.advance() {
+            Some('\'') => TokenKind::CharLiteral(value),
+            None => TokenKind::Unknown("unterminated character literal".to_string()),
+            _ => {
+                // Consume up to the next quote (or EOF) so lexing can
+                // resume cleanly after the malformed literal.
+                while !matches!(self.advance(), None | Some('\'')) {}
+                TokenKind::Unknown("character literal contains more than one character".to_string())
+            }
+        }
+    }
+}
+
+impl<'a> Iterator for Tokenizer<'a> {
+    type Item = Token;
+
+    fn next(&mut self) -> Option<Token> {
+        use TokenKind::*;
+
+        loop {
+            while matches!(self.peek(), Some(c) if c.is_whitespace()) {
+                self.advance();
+            }
+
+            if self.emit_comments || !self.is_comment_start() {
+                break;
+            }
+
+            let start = (self.offset, self.line, self.column);
+            if !self.skip_comment() {
+                let end = (self.offset, self.line, self.column);
+                return Some(Token {
+                    kind: Unknown("unterminated block comment".to_string()),
+                    span: Span::new(start, end),
+                });
+            }
+        }
+
+        let start = (self.offset, self.line, self.column);
+        let first = self.advance()?;
+
+        let kind = match first {
+            '{' => OpenBrace,
+            '}' => CloseBrace,
+            '(' => OpenParenthesis,
+            ')' => CloseParenthesis,
+            ';' => Semicolon,
+
+            '/' if matches!(self.peek(), Some('/') | Some('*')) => self.read_comment(),
+
+            '"' => self.read_string_literal(),
+            '\'' => self.read_char_literal(),
+
+            '0'..='9' => {
+                let mut number = first.to_digit(10).unwrap() as u64;
+                let mut radix = if first == '0' { None } else { Some(10) };
+
+                loop {
+                    match self.peek() {
+                        Some(d) if radix.is_none() && matches!(d, 'b' | 'o' | 'x') => {
+                            radix = Some(match d {
+                                'b' => 2,
+                                'o' => 8,
+                                'x' => 16,
+                                _ => unreachable!(),
+                            });
+                            self.advance();
+                        }
+                        Some(d) if radix.is_none() && d.is_ascii_digit() => {
+                            radix = Some(10);
+                            number = 10 * number + d.to_digit(10).unwrap() as u64;
+                            self.advance();
+                        }
+                        Some(d) => {
+                            let r = radix.unwrap_or(10);
+                            match d.to_digit(r) {
+                                Some(digit) => {
+                                    number = r as u64 * number + digit as u64;
+                                    self.advance();
+                                }
+                                None => break,
+                            }
+                        }
+                        None => break,
+                    }
+                }
+
+                Integer(number)
+            }
+
+            c if c.is_alphabetic() || c == '_' => {
+                let mut ident = c.to_string();
+                while let Some(d) = self.peek() {
+                    if d.is_alphanumeric() || d == '_' {
+                        ident.push(d);
+                        self.advance();
+                    } else {
+                        break;
+                    }
+                }
+                string_to_token(&ident)
+            }
+
+            c => Unknown(format!("unexpected character: {}", c)),
+        };
+
+        let end = (self.offset, self.line, self.column);
+        Some(Token {
+            kind,
+            span: Span::new(start, end),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn kinds(source: &str) -> Vec<TokenKind> {
+        Tokenizer::new(source).map(|token| token.kind).collect()
+    }
+
+    fn first_kind(source: &str) -> TokenKind {
+        Tokenizer::new(source).next().unwrap().kind
+    }
+
+    #[test]
+    fn lexes_an_in_memory_str_without_a_reader() {
+        assert_eq!(
+            kinds("Int main() { Return 1; }"),
+            vec![
+                TokenKind::IntKeyword,
+                TokenKind::Identifier("main".to_string()),
+                TokenKind::OpenParenthesis,
+                TokenKind::CloseParenthesis,
+                TokenKind::OpenBrace,
+                TokenKind::ReturnKeyword,
+                TokenKind::Integer(1),
+                TokenKind::Semicolon,
+                TokenKind::CloseBrace,
+            ]
+        );
+    }
+
+    #[test]
+    fn unknown_token_does_not_abort_the_stream() {
+        // The stray '@' becomes an `Unknown` token, but lexing keeps going
+        // and still yields the `Return` that follows it.
+        let kinds = kinds("@ Return");
+        assert_eq!(
+            kinds,
+            vec![
+                TokenKind::Unknown("unexpected character: @".to_string()),
+                TokenKind::ReturnKeyword,
+            ]
+        );
+    }
+
+    #[test]
+    fn collects_multiple_errors_in_one_pass() {
+        let errors: Vec<TokenKind> = kinds("@ # $")
+            .into_iter()
+            .filter(|kind| matches!(kind, TokenKind::Unknown(_)))
+            .collect();
+        assert_eq!(errors.len(), 3);
+    }
+
+    #[test]
+    fn skips_comments_by_default() {
+        assert_eq!(kinds("// hi\nReturn"), vec![TokenKind::ReturnKeyword]);
+        assert_eq!(
+            kinds("/* hi */ Return"),
+            vec![TokenKind::ReturnKeyword]
+        );
+    }
+
+    #[test]
+    fn unterminated_block_comment_is_unknown_even_when_skipped() {
+        // Regression test for a block comment with no closing `*/`: this
+        // must surface as an `Unknown` token rather than being silently
+        // swallowed to end of input, same as when `keep_comments` is set.
+        assert_eq!(
+            first_kind("/* never closed"),
+            TokenKind::Unknown("unterminated block comment".to_string())
+        );
+    }
+
+    #[test]
+    fn decodes_each_string_escape() {
+        assert_eq!(
+            first_kind(r#""\n\t\\\"\'\0""#),
+            TokenKind::StringLiteral("\n\t\\\"\'\0".to_string())
+        );
+    }
+
+    #[test]
+    fn decodes_a_hex_escape() {
+        assert_eq!(
+            first_kind(r#""\x41\x42""#),
+            TokenKind::StringLiteral("AB".to_string())
+        );
+    }
+
+    #[test]
+    fn invalid_hex_escape_is_unknown() {
+        assert_eq!(
+            first_kind(r#""a\xZZb""#),
+            TokenKind::Unknown("invalid hex escape: \\xZZ".to_string())
+        );
+    }
+
+    #[test]
+    fn unrecognized_escape_keeps_the_literal_character() {
+        assert_eq!(
+            first_kind(r#""\q""#),
+            TokenKind::StringLiteral("q".to_string())
+        );
+    }
+
+    #[test]
+    fn unterminated_string_literal_is_unknown() {
+        assert_eq!(
+            first_kind("\"abc"),
+            TokenKind::Unknown("unterminated string literal".to_string())
+        );
+        assert_eq!(
+            first_kind("\"abc\\"),
+            TokenKind::Unknown("unterminated string literal".to_string())
+        );
+    }
+
+    #[test]
+    fn char_literal_with_and_without_escape() {
+        assert_eq!(first_kind("'a'"), TokenKind::CharLiteral('a'));
+        assert_eq!(first_kind(r"'\n'"), TokenKind::CharLiteral('\n'));
+    }
+
+    #[test]
+    fn char_literal_missing_closing_quote_is_unknown() {
+        // Ran out of input before a closing `'`, right after the first
+        // character: reported as unterminated, not as a multi-character
+        // literal.
+        assert_eq!(
+            first_kind("'a"),
+            TokenKind::Unknown("unterminated character literal".to_string())
+        );
+    }
+
+    #[test]
+    fn char_literal_unterminated_mid_escape_is_unknown() {
+        assert_eq!(
+            first_kind("'\\"),
+            TokenKind::Unknown("unterminated character literal".to_string())
+        );
+    }
+
+    #[test]
+    fn multi_character_literal_is_unknown() {
+        assert_eq!(
+            first_kind("'ab'"),
+            TokenKind::Unknown(
+                "character literal contains more than one character".to_string()
+            )
+        );
+    }
+}
@@ -0,0 +1,179 @@
+use crate::ast::{Expression, Function, Program, Statement, Type};
+use crate::diagnostics::ParseError;
+use crate::lexer::{Token, TokenKind};
+use crate::span::Span;
+
+/// A hand-written recursive-descent parser with a single token of
+/// lookahead, consuming the flat token stream the lexer produces and
+/// building the AST defined in `ast`.
+pub struct Parser<'a> {
+    tokens: &'a [Token],
+    position: usize,
+    eof_span: Span,
+}
+
+impl<'a> Parser<'a> {
+    /// `eof_span` is pointed at when the parser runs out of tokens, so
+    /// "unexpected end of input" errors still have somewhere to underline.
+    pub fn new(tokens: &'a [Token], eof_span: Span) -> Self {
+        Parser {
+            tokens,
+            position: 0,
+            eof_span,
+        }
+    }
+
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.position)
+    }
+
+    fn span(&self) -> Span {
+        self.peek().map(|token| token.span).unwrap_or(self.eof_span)
+    }
+
+    fn advance(&mut self) -> Option<&Token> {
+        let token = self.tokens.get(self.position);
+        if token.is_some() {
+            self.position += 1;
+        }
+        token
+    }
+
+    /// Consumes the next token if its kind matches `kind` exactly,
+    /// otherwise produces a span-aware parse error naming what was
+    /// expected instead.
+    fn expect(&mut self, kind: TokenKind) -> Result<&Token, ParseError> {
+        let span = self.span();
+        match self.peek() {
+            Some(token) if token.kind == kind => Ok(self.advance().unwrap()),
+            Some(token) => Err(ParseError::new(
+                format!("Expected {:?}, found {:?}", kind, token.kind),
+                span,
+            )),
+            None => Err(ParseError::new(
+                format!("Expected {:?}, found end of input", kind),
+                span,
+            )),
+        }
+    }
+
+    fn expect_identifier(&mut self) -> Result<String, ParseError> {
+        let span = self.span();
+        match self.advance().map(|token| token.kind.clone()) {
+            Some(TokenKind::Identifier(name)) => Ok(name),
+            Some(kind) => Err(ParseError::new(format!("Expected identifier, found {:?}", kind), span)),
+            None => Err(ParseError::new("Expected identifier, found end of input".to_string(), span)),
+        }
+    }
+
+    pub fn parse_program(&mut self) -> Result<Program, ParseError> {
+        let mut functions = Vec::new();
+        while self.peek().is_some() {
+            functions.push(self.parse_function()?);
+        }
+        Ok(Program { functions })
+    }
+
+    fn parse_function(&mut self) -> Result<Function, ParseError> {
+        self.expect(TokenKind::IntKeyword)?;
+        let return_type = Type::Int;
+        let name = self.expect_identifier()?;
+
+        self.expect(TokenKind::OpenParenthesis)?;
+        self.expect(TokenKind::CloseParenthesis)?;
+        self.expect(TokenKind::OpenBrace)?;
+
+        let mut body = Vec::new();
+        while !matches!(self.peek().map(|token| &token.kind), Some(TokenKind::CloseBrace)) {
+            body.push(self.parse_statement()?);
+        }
+
+        self.expect(TokenKind::CloseBrace)?;
+
+        Ok(Function {
+            return_type,
+            name,
+            body,
+        })
+    }
+
+    fn parse_statement(&mut self) -> Result<Statement, ParseError> {
+        self.expect(TokenKind::ReturnKeyword)?;
+        let expression = self.parse_expression()?;
+        self.expect(TokenKind::Semicolon)?;
+        Ok(Statement::Return(expression))
+    }
+
+    fn parse_expression(&mut self) -> Result<Expression, ParseError> {
+        let span = self.span();
+        match self.advance().map(|token| token.kind.clone()) {
+            Some(TokenKind::Integer(value)) => Ok(Expression::Constant(value)),
+            Some(kind) => Err(ParseError::new(format!("Expected expression, found {:?}", kind), span)),
+            None => Err(ParseError::new("Expected expression, found end of input".to_string(), span)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::lexer::Tokenizer;
+
+    fn parse(source: &str) -> Result<Program, ParseError> {
+        let tokens: Vec<Token> = Tokenizer::new(source).collect();
+        let eof_span = tokens
+            .last()
+            .map(|token| token.span)
+            .unwrap_or_else(|| Span::new((0, 1, 1), (0, 1, 1)));
+        Parser::new(&tokens, eof_span).parse_program()
+    }
+
+    #[test]
+    fn parses_a_minimal_program() {
+        let program = parse("Int main() { Return 1; }").unwrap();
+        assert_eq!(
+            program,
+            Program {
+                functions: vec![Function {
+                    return_type: Type::Int,
+                    name: "main".to_string(),
+                    body: vec![Statement::Return(Expression::Constant(1))],
+                }],
+            }
+        );
+    }
+
+    #[test]
+    fn expect_mismatch_names_what_it_found() {
+        // A missing `;` means `expect(Semicolon)` sees the `}` instead.
+        let error = parse("Int main() { Return 1 }").unwrap_err();
+        assert_eq!(error.message, "Expected Semicolon, found CloseBrace");
+    }
+
+    #[test]
+    fn expect_identifier_mismatch_names_what_it_found() {
+        let error = parse("Int 123() { Return 1; }").unwrap_err();
+        assert_eq!(error.message, "Expected identifier, found Integer(123)");
+    }
+
+    #[test]
+    fn end_of_input_while_expecting_a_token() {
+        let error = parse("Int main(").unwrap_err();
+        assert_eq!(error.message, "Expected CloseParenthesis, found end of input");
+    }
+
+    #[test]
+    fn end_of_input_while_expecting_an_expression() {
+        let error = parse("Int main() { Return").unwrap_err();
+        assert_eq!(error.message, "Expected expression, found end of input");
+    }
+
+    #[test]
+    fn trailing_garbage_after_a_function_is_a_parse_error() {
+        // Once `main` is fully parsed, `parse_program` loops back into
+        // `parse_function`, which expects another `Int` and instead finds
+        // the leftover `Return`.
+        let error = parse("Int main() { Return 1; } Return 2;").unwrap_err();
+        assert_eq!(error.message, "Expected IntKeyword, found ReturnKeyword");
+    }
+}
@@ -0,0 +1,47 @@
+use rustyline::error::ReadlineError;
+use rustyline::DefaultEditor;
+
+use crate::diagnostics;
+use crate::lexer::{Token, Tokenizer};
+use crate::parser::Parser;
+use crate::span::Span;
+
+/// Interactive "lex and parse this line" loop, used when the compiler is
+/// run with no filename. Each line is tokenized and, if it parses as a
+/// complete program, has its AST printed too -- handy for quickly
+/// inspecting how a snippet tokenizes and parses without a temp file.
+/// Line editing and history (including up/down recall) are handled by
+/// `rustyline`.
+pub fn run() -> rustyline::Result<()> {
+    let mut editor = DefaultEditor::new()?;
+
+    loop {
+        match editor.readline("> ") {
+            Ok(line) => {
+                if line.is_empty() {
+                    continue;
+                }
+
+                editor.add_history_entry(line.as_str())?;
+
+                let tokens: Vec<Token> = Tokenizer::new(&line).collect();
+                println!("{:?}", tokens);
+
+                let eof_span = tokens
+                    .last()
+                    .map(|token| token.span)
+                    .unwrap_or_else(|| Span::new((0, 1, 1), (0, 1, 1)));
+
+                match Parser::new(&tokens, eof_span).parse_program() {
+                    Ok(program) => println!("{:?}", program),
+                    Err(error) => eprint!("{}", diagnostics::report("<repl>", &line, &error)),
+                }
+            }
+            Err(ReadlineError::Interrupted) => continue,
+            Err(ReadlineError::Eof) => break,
+            Err(error) => return Err(error),
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,23 @@
+/// A byte-range position in the original source, together with the
+/// human-facing line/column of its start (both 1-indexed).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Span {
+    pub start: usize,
+    pub end: usize,
+    pub line: usize,
+    pub column: usize,
+}
+
+impl Span {
+    /// A span running from `start` up to (but not including) `end`.
+    pub fn new(start: (usize, usize, usize), end: (usize, usize, usize)) -> Self {
+        let (start_offset, line, column) = start;
+        let (end_offset, ..) = end;
+        Span {
+            start: start_offset,
+            end: end_offset,
+            line,
+            column,
+        }
+    }
+}